@@ -0,0 +1,259 @@
+use crate::error::{Error, Result};
+use crate::trace::TraceContext;
+use reqwest::{Client, Method, StatusCode};
+use serde_json::{json, Value};
+use url::form_urlencoded;
+
+/// Thin wrapper around the server's admin HTTP API. Every `admin_*` method maps to
+/// one endpoint; response bodies are returned as-is (`serde_json::Value`) so callers
+/// can decide how to render them.
+#[derive(Clone)]
+pub struct HttpClient {
+    base_url: String,
+    admin_token: String,
+    inner: Client,
+}
+
+impl HttpClient {
+    pub fn new(base_url: impl Into<String>, admin_token: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            admin_token: admin_token.into(),
+            inner: Client::new(),
+        }
+    }
+
+    /// Issues one HTTP request, attaching the `traceparent`/`X-Request-Id` headers
+    /// when a trace context is supplied so the call can be correlated with
+    /// server-side logs.
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut req = self.inner.request(method, url).bearer_auth(&self.admin_token);
+        if let Some(trace) = trace {
+            req = req
+                .header("traceparent", trace.traceparent())
+                .header("X-Request-Id", trace.request_id());
+        }
+        if let Some(body) = &body {
+            req = req.json(body);
+        }
+
+        let response = req.send().await.map_err(|err| Error::Other(err.to_string()))?;
+        let status = response.status();
+        let value: Value = response.json().await.unwrap_or(Value::Null);
+
+        match status {
+            StatusCode::UNAUTHORIZED => Err(Error::Unauthorized(value.to_string())),
+            StatusCode::NOT_FOUND => Err(Error::NotFound(value.to_string())),
+            s if s.is_success() => Ok(value),
+            s => Err(Error::Other(format!("{s}: {value}"))),
+        }
+    }
+
+    pub async fn admin_create_account(
+        &self,
+        account_id: &str,
+        admin_user_id: &str,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(
+            Method::POST,
+            "/admin/accounts",
+            Some(json!({"account_id": account_id, "admin_user_id": admin_user_id})),
+            trace,
+        )
+        .await
+    }
+
+    pub async fn admin_list_accounts(&self, trace: Option<&TraceContext>) -> Result<Value> {
+        self.request(Method::GET, "/admin/accounts", None, trace).await
+    }
+
+    pub async fn admin_delete_account(
+        &self,
+        account_id: &str,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(Method::DELETE, &format!("/admin/accounts/{account_id}"), None, trace)
+            .await
+    }
+
+    pub async fn admin_register_user(
+        &self,
+        account_id: &str,
+        user_id: &str,
+        role: &str,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(
+            Method::POST,
+            &format!("/admin/accounts/{account_id}/users"),
+            Some(json!({"user_id": user_id, "role": role})),
+            trace,
+        )
+        .await
+    }
+
+    pub async fn admin_list_users(&self, account_id: &str, trace: Option<&TraceContext>) -> Result<Value> {
+        self.request(Method::GET, &format!("/admin/accounts/{account_id}/users"), None, trace)
+            .await
+    }
+
+    pub async fn admin_remove_user(
+        &self,
+        account_id: &str,
+        user_id: &str,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(
+            Method::DELETE,
+            &format!("/admin/accounts/{account_id}/users/{user_id}"),
+            None,
+            trace,
+        )
+        .await
+    }
+
+    pub async fn admin_set_role(
+        &self,
+        account_id: &str,
+        user_id: &str,
+        role: &str,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(
+            Method::PUT,
+            &format!("/admin/accounts/{account_id}/users/{user_id}/role"),
+            Some(json!({"role": role})),
+            trace,
+        )
+        .await
+    }
+
+    pub async fn admin_regenerate_key(
+        &self,
+        account_id: &str,
+        user_id: &str,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(
+            Method::POST,
+            &format!("/admin/accounts/{account_id}/users/{user_id}/key"),
+            None,
+            trace,
+        )
+        .await
+    }
+
+    /// Creates an invitation token. When `dry_run` is set, the server validates and
+    /// reports the request without persisting a token — the same no-op path
+    /// `diagnostics` uses to confirm invitation delivery is configured.
+    pub async fn admin_create_invitation_token(
+        &self,
+        max_uses: Option<i64>,
+        expires_at: Option<&str>,
+        dry_run: bool,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(
+            Method::POST,
+            "/admin/invitation-tokens",
+            Some(json!({"max_uses": max_uses, "expires_at": expires_at, "dry_run": dry_run})),
+            trace,
+        )
+        .await
+    }
+
+    pub async fn admin_list_invitation_tokens(&self, trace: Option<&TraceContext>) -> Result<Value> {
+        self.request(Method::GET, "/admin/invitation-tokens", None, trace).await
+    }
+
+    pub async fn admin_revoke_invitation_token(
+        &self,
+        token_id: &str,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(
+            Method::DELETE,
+            &format!("/admin/invitation-tokens/{token_id}"),
+            None,
+            trace,
+        )
+        .await
+    }
+
+    pub async fn register_account(
+        &self,
+        invitation_token: &str,
+        account_id: &str,
+        admin_user_id: &str,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        self.request(
+            Method::POST,
+            "/register",
+            Some(json!({
+                "invitation_token": invitation_token,
+                "account_id": account_id,
+                "admin_user_id": admin_user_id,
+            })),
+            trace,
+        )
+        .await
+    }
+
+    /// Fetches the server's own version string, used by `diagnostics` to report
+    /// version skew against this CLI.
+    pub async fn server_version(&self, trace: Option<&TraceContext>) -> Result<String> {
+        let value = self.request(Method::GET, "/version", None, trace).await?;
+        value
+            .get("version")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| Error::Other("server response missing version field".into()))
+    }
+
+    /// Fetches the audit trail of administrative mutations, optionally filtered by
+    /// account, user, event type, and a `since` timestamp/cursor. Filter values are
+    /// percent-encoded, since a `since` cursor or a crafted filter can contain `&`,
+    /// `=`, or `+` that would otherwise corrupt the query string.
+    pub async fn admin_list_events(
+        &self,
+        account_id: Option<&str>,
+        user_id: Option<&str>,
+        event_type: Option<&str>,
+        since: Option<&str>,
+        limit: Option<i64>,
+        trace: Option<&TraceContext>,
+    ) -> Result<Value> {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        if let Some(account_id) = account_id {
+            query.append_pair("account_id", account_id);
+        }
+        if let Some(user_id) = user_id {
+            query.append_pair("user_id", user_id);
+        }
+        if let Some(event_type) = event_type {
+            query.append_pair("event_type", event_type);
+        }
+        if let Some(since) = since {
+            query.append_pair("since", since);
+        }
+        if let Some(limit) = limit {
+            query.append_pair("limit", &limit.to_string());
+        }
+        let query = query.finish();
+        let path = if query.is_empty() {
+            "/admin/events".to_string()
+        } else {
+            format!("/admin/events?{query}")
+        };
+        self.request(Method::GET, &path, None, trace).await
+    }
+}
@@ -1,37 +1,68 @@
 use crate::client::HttpClient;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::output::{output_success, OutputFormat};
+use crate::trace::TraceContext;
+use serde::Deserialize;
 use serde_json::json;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Instant;
+use tracing::instrument;
 
+/// Stamps the trace/request id onto a JSON response envelope so operators can
+/// correlate CLI output with the server-side logs a request produced.
+fn annotate_trace(mut value: serde_json::Value, trace: Option<&TraceContext>) -> serde_json::Value {
+    if let (Some(trace), Some(object)) = (trace, value.as_object_mut()) {
+        object.insert("trace_id".into(), json!(trace.trace_id_hex()));
+        object.insert("request_id".into(), json!(trace.request_id()));
+    }
+    value
+}
+
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn create_account(
     client: &HttpClient,
     account_id: &str,
     admin_user_id: &str,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_create_account(account_id, admin_user_id).await?;
-    output_success(&response, output_format, compact);
+    let response = client.admin_create_account(account_id, admin_user_id, trace).await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn list_accounts(
     client: &HttpClient,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_list_accounts().await?;
-    output_success(&response, output_format, compact);
+    let response = client.admin_list_accounts(trace).await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn delete_account(
     client: &HttpClient,
     account_id: &str,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_delete_account(account_id).await?;
+    let response = client.admin_delete_account(account_id, trace).await?;
     let result = if response.is_null()
         || response.as_object().map(|o| o.is_empty()).unwrap_or(false)
     {
@@ -39,42 +70,57 @@ pub async fn delete_account(
     } else {
         response
     };
-    output_success(&result, output_format, compact);
+    output_success(&annotate_trace(result, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn register_user(
     client: &HttpClient,
     account_id: &str,
     user_id: &str,
     role: &str,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_register_user(account_id, user_id, role).await?;
-    output_success(&response, output_format, compact);
+    let response = client.admin_register_user(account_id, user_id, role, trace).await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn list_users(
     client: &HttpClient,
     account_id: &str,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_list_users(account_id).await?;
-    output_success(&response, output_format, compact);
+    let response = client.admin_list_users(account_id, trace).await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn remove_user(
     client: &HttpClient,
     account_id: &str,
     user_id: &str,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_remove_user(account_id, user_id).await?;
+    let response = client.admin_remove_user(account_id, user_id, trace).await?;
     let result = if response.is_null()
         || response.as_object().map(|o| o.is_empty()).unwrap_or(false)
     {
@@ -82,64 +128,91 @@ pub async fn remove_user(
     } else {
         response
     };
-    output_success(&result, output_format, compact);
+    output_success(&annotate_trace(result, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn set_role(
     client: &HttpClient,
     account_id: &str,
     user_id: &str,
     role: &str,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_set_role(account_id, user_id, role).await?;
-    output_success(&response, output_format, compact);
+    let response = client.admin_set_role(account_id, user_id, role, trace).await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn regenerate_key(
     client: &HttpClient,
     account_id: &str,
     user_id: &str,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_regenerate_key(account_id, user_id).await?;
-    output_success(&response, output_format, compact);
+    let response = client.admin_regenerate_key(account_id, user_id, trace).await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn create_invitation_token(
     client: &HttpClient,
     max_uses: Option<i64>,
     expires_at: Option<&str>,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_create_invitation_token(max_uses, expires_at).await?;
-    output_success(&response, output_format, compact);
+    let response = client
+        .admin_create_invitation_token(max_uses, expires_at, false, trace)
+        .await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn list_invitation_tokens(
     client: &HttpClient,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_list_invitation_tokens().await?;
-    output_success(&response, output_format, compact);
+    let response = client.admin_list_invitation_tokens(trace).await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn revoke_invitation_token(
     client: &HttpClient,
     token_id: &str,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.admin_revoke_invitation_token(token_id).await?;
+    let response = client.admin_revoke_invitation_token(token_id, trace).await?;
     let result = if response.is_null()
         || response.as_object().map(|o| o.is_empty()).unwrap_or(false)
     {
@@ -147,19 +220,526 @@ pub async fn revoke_invitation_token(
     } else {
         response
     };
-    output_success(&result, output_format, compact);
+    output_success(&annotate_trace(result, trace), output_format, compact);
     Ok(())
 }
 
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
 pub async fn register_account(
     client: &HttpClient,
     invitation_token: &str,
     account_id: &str,
     admin_user_id: &str,
+    trace: Option<&TraceContext>,
+    output_format: OutputFormat,
+    compact: bool,
+) -> Result<()> {
+    let response = client
+        .register_account(invitation_token, account_id, admin_user_id, trace)
+        .await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct DesiredUser {
+    user_id: String,
+    role: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct DesiredAccount {
+    account_id: String,
+    admin_user_id: String,
+    #[serde(default)]
+    users: Vec<DesiredUser>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Manifest {
+    #[serde(default)]
+    accounts: Vec<DesiredAccount>,
+}
+
+fn json_str<'a>(value: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(|v| v.as_str())
+}
+
+/// Parses a manifest as YAML when `manifest_path` has a `.yaml`/`.yml` extension,
+/// and as JSON otherwise.
+fn parse_manifest(manifest_path: &Path, raw: &str) -> Result<Manifest> {
+    match manifest_path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml") | Some("yml") => Ok(serde_yaml::from_str(raw)?),
+        _ => Ok(serde_json::from_str(raw)?),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum UserAction {
+    Create,
+    SetRole,
+    Unchanged,
+}
+
+/// Decides what a single desired user needs, given the role it currently holds on
+/// the server (`None` if the user isn't registered yet).
+fn plan_user_action(current_role: Option<&str>, desired_role: &str) -> UserAction {
+    match current_role {
+        None => UserAction::Create,
+        Some(role) if role != desired_role => UserAction::SetRole,
+        Some(_) => UserAction::Unchanged,
+    }
+}
+
+/// Decides whether a user present on the server but absent from the manifest should
+/// be pruned. The account's own `admin_user_id` is never pruned, since removing it
+/// would leave the account without an administrator.
+fn should_prune_user(user_id: &str, admin_user_id: &str, desired_user_ids: &HashSet<&str>) -> bool {
+    user_id != admin_user_id && !desired_user_ids.contains(user_id)
+}
+
+/// Reconciles the server's accounts/users/roles to match a declarative manifest.
+///
+/// Accounts and users present in the manifest but missing on the server are created;
+/// users whose role differs are updated; with `prune` set, users present on the server
+/// but absent from the manifest are removed (the account's own `admin_user_id` is never
+/// pruned). Each item is applied independently so a single failure does not abort the
+/// rest of the run; failures are collected into the returned summary instead.
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
+pub async fn apply_manifest(
+    client: &HttpClient,
+    manifest_path: &Path,
+    prune: bool,
+    dry_run: bool,
+    trace: Option<&TraceContext>,
     output_format: OutputFormat,
     compact: bool,
 ) -> Result<()> {
-    let response = client.register_account(invitation_token, account_id, admin_user_id).await?;
-    output_success(&response, output_format, compact);
+    let raw = std::fs::read_to_string(manifest_path)?;
+    let manifest = parse_manifest(manifest_path, &raw)?;
+    let result = run_apply(client, &manifest, prune, dry_run, trace).await?;
+    output_success(&annotate_trace(result, trace), output_format, compact);
     Ok(())
 }
+
+/// Core of `apply_manifest`, split out so the `serve` HTTP gateway can reconcile a
+/// manifest received as a request body without going through the filesystem.
+/// Returns the computed plan when `dry_run` is set, or the `{created, updated,
+/// removed, unchanged, errors}` summary otherwise.
+pub(crate) async fn run_apply(
+    client: &HttpClient,
+    manifest: &Manifest,
+    prune: bool,
+    dry_run: bool,
+    trace: Option<&TraceContext>,
+) -> Result<serde_json::Value> {
+    let existing_accounts = client.admin_list_accounts(trace).await?;
+    let existing_account_ids: HashSet<String> = existing_accounts
+        .as_array()
+        .map(|accounts| {
+            accounts
+                .iter()
+                .filter_map(|a| json_str(a, "account_id").map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut created = 0u32;
+    let mut updated = 0u32;
+    let mut removed = 0u32;
+    let mut unchanged = 0u32;
+    let mut errors: Vec<String> = Vec::new();
+    let mut plan: Vec<serde_json::Value> = Vec::new();
+
+    for account in &manifest.accounts {
+        if !existing_account_ids.contains(&account.account_id) {
+            plan.push(json!({"action": "create_account", "account_id": account.account_id}));
+            if !dry_run {
+                if let Err(err) = client
+                    .admin_create_account(&account.account_id, &account.admin_user_id, trace)
+                    .await
+                {
+                    errors.push(format!("create_account {}: {err}", account.account_id));
+                    continue;
+                }
+            }
+            created += 1;
+        }
+
+        let existing_users = if dry_run && !existing_account_ids.contains(&account.account_id) {
+            json!([])
+        } else {
+            match client.admin_list_users(&account.account_id, trace).await {
+                Ok(users) => users,
+                Err(err) => {
+                    errors.push(format!("list_users {}: {err}", account.account_id));
+                    continue;
+                }
+            }
+        };
+
+        let mut desired_user_ids: HashSet<&str> = HashSet::new();
+        for user in &account.users {
+            desired_user_ids.insert(user.user_id.as_str());
+
+            let current_role = existing_users
+                .as_array()
+                .and_then(|users| {
+                    users
+                        .iter()
+                        .find(|u| json_str(u, "user_id") == Some(user.user_id.as_str()))
+                })
+                .and_then(|u| json_str(u, "role"));
+
+            match plan_user_action(current_role, &user.role) {
+                UserAction::Create => {
+                    plan.push(json!({
+                        "action": "register_user",
+                        "account_id": account.account_id,
+                        "user_id": user.user_id,
+                        "role": user.role,
+                    }));
+                    if !dry_run {
+                        if let Err(err) = client
+                            .admin_register_user(&account.account_id, &user.user_id, &user.role, trace)
+                            .await
+                        {
+                            errors.push(format!(
+                                "register_user {}/{}: {err}",
+                                account.account_id, user.user_id
+                            ));
+                            continue;
+                        }
+                    }
+                    created += 1;
+                }
+                UserAction::SetRole => {
+                    plan.push(json!({
+                        "action": "set_role",
+                        "account_id": account.account_id,
+                        "user_id": user.user_id,
+                        "role": user.role,
+                    }));
+                    if !dry_run {
+                        if let Err(err) = client
+                            .admin_set_role(&account.account_id, &user.user_id, &user.role, trace)
+                            .await
+                        {
+                            errors.push(format!(
+                                "set_role {}/{}: {err}",
+                                account.account_id, user.user_id
+                            ));
+                            continue;
+                        }
+                    }
+                    updated += 1;
+                }
+                UserAction::Unchanged => unchanged += 1,
+            }
+        }
+
+        if prune {
+            if let Some(users) = existing_users.as_array() {
+                for user in users {
+                    let Some(user_id) = json_str(user, "user_id") else {
+                        continue;
+                    };
+                    if !should_prune_user(user_id, &account.admin_user_id, &desired_user_ids) {
+                        continue;
+                    }
+                    plan.push(json!({
+                        "action": "remove_user",
+                        "account_id": account.account_id,
+                        "user_id": user_id,
+                    }));
+                    if !dry_run {
+                        if let Err(err) =
+                            client.admin_remove_user(&account.account_id, user_id, trace).await
+                        {
+                            errors.push(format!(
+                                "remove_user {}/{}: {err}",
+                                account.account_id, user_id
+                            ));
+                            continue;
+                        }
+                    }
+                    removed += 1;
+                }
+            }
+        }
+    }
+
+    if dry_run {
+        return Ok(json!({"plan": plan}));
+    }
+
+    Ok(json!({
+        "created": created,
+        "updated": updated,
+        "removed": removed,
+        "unchanged": unchanged,
+        "errors": errors,
+    }))
+}
+
+struct DiagnosticCheck {
+    check: &'static str,
+    status: &'static str,
+    detail: String,
+    critical: bool,
+}
+
+impl DiagnosticCheck {
+    fn ok(check: &'static str, detail: impl Into<String>) -> Self {
+        Self { check, status: "ok", detail: detail.into(), critical: false }
+    }
+
+    fn warn(check: &'static str, detail: impl Into<String>) -> Self {
+        Self { check, status: "warn", detail: detail.into(), critical: false }
+    }
+
+    fn fail(check: &'static str, detail: impl Into<String>, critical: bool) -> Self {
+        Self { check, status: "fail", detail: detail.into(), critical }
+    }
+}
+
+/// Runs a battery of pre-flight checks against the configured endpoint: connectivity
+/// and latency, server/client version compatibility, whether the current admin
+/// credentials authenticate, and whether invitation delivery is configured. Every
+/// check is collected into a single report rather than failing fast, so a single
+/// `diagnostics` run tells the whole story; a non-zero exit is reserved for when a
+/// critical check fails, so this can gate CI.
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
+pub async fn diagnostics(
+    client: &HttpClient,
+    trace: Option<&TraceContext>,
+    output_format: OutputFormat,
+    compact: bool,
+) -> Result<()> {
+    let mut checks = Vec::new();
+
+    // A single probe against `admin_list_accounts` doubles as the connectivity check
+    // and the auth check (200 vs. 401), since both ride on the same request. Any
+    // error other than a 401, including a non-success HTTP response, is reported as
+    // a connectivity failure.
+    let started = Instant::now();
+    match client.admin_list_accounts(trace).await {
+        Ok(_) => {
+            checks.push(DiagnosticCheck::ok(
+                "connectivity",
+                format!("reachable in {}ms", started.elapsed().as_millis()),
+            ));
+            checks.push(DiagnosticCheck::ok("auth", "admin credentials authenticate (200)"));
+        }
+        Err(Error::Unauthorized(_)) => {
+            checks.push(DiagnosticCheck::ok(
+                "connectivity",
+                format!("reachable in {}ms", started.elapsed().as_millis()),
+            ));
+            checks.push(DiagnosticCheck::fail("auth", "server rejected credentials (401)", true));
+        }
+        Err(err) => {
+            checks.push(DiagnosticCheck::fail("connectivity", format!("{err}"), true));
+        }
+    }
+
+    let client_version = env!("CARGO_PKG_VERSION");
+    match client.server_version(trace).await {
+        Ok(server_version) => {
+            if server_version == client_version {
+                checks.push(DiagnosticCheck::ok(
+                    "version",
+                    format!("server {server_version} matches cli {client_version}"),
+                ));
+            } else {
+                checks.push(DiagnosticCheck::warn(
+                    "version",
+                    format!("server {server_version} differs from cli {client_version}"),
+                ));
+            }
+        }
+        Err(err) => checks.push(DiagnosticCheck::warn("version", format!("could not fetch server version: {err}"))),
+    }
+
+    // No dedicated delivery-status endpoint exists; a dry-run invitation-token call
+    // exercises the same configuration check without persisting a token.
+    match client.admin_create_invitation_token(None, None, true, trace).await {
+        Ok(status) => {
+            let configured = status.get("email_delivery_configured").and_then(|v| v.as_bool()).unwrap_or(false);
+            if configured {
+                checks.push(DiagnosticCheck::ok("invitation_delivery", "email delivery is configured"));
+            } else {
+                checks.push(DiagnosticCheck::warn("invitation_delivery", "email delivery is not configured"));
+            }
+        }
+        Err(err) => checks.push(DiagnosticCheck::warn(
+            "invitation_delivery",
+            format!("could not determine delivery status: {err}"),
+        )),
+    }
+
+    let has_critical_failure = checks.iter().any(|c| c.status == "fail" && c.critical);
+
+    let report: Vec<_> = checks
+        .iter()
+        .map(|c| json!({"check": c.check, "status": c.status, "detail": c.detail}))
+        .collect();
+    output_success(&annotate_trace(json!({"checks": report}), trace), output_format, compact);
+
+    if has_critical_failure {
+        return Err(Error::Other("one or more critical diagnostics checks failed".into()));
+    }
+    Ok(())
+}
+
+/// Lists audit events recorded for administrative mutations (account/user creation
+/// and removal, role changes, key regeneration, token revocation), optionally
+/// filtered by account, user, event type, and a `since` timestamp/cursor.
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
+pub async fn list_events(
+    client: &HttpClient,
+    account_id: Option<&str>,
+    user_id: Option<&str>,
+    event_type: Option<&str>,
+    since: Option<&str>,
+    limit: Option<i64>,
+    trace: Option<&TraceContext>,
+    output_format: OutputFormat,
+    compact: bool,
+) -> Result<()> {
+    let response = client
+        .admin_list_events(account_id, user_id, event_type, since, limit, trace)
+        .await?;
+    output_success(&annotate_trace(response, trace), output_format, compact);
+    Ok(())
+}
+
+/// Polls the audit trail every two seconds and prints newly appended events as they
+/// arrive, so operators can answer "who changed this role and when" as it happens
+/// rather than after the fact. Each poll is an ordinary request; the server is not
+/// asked to hold it open. Runs until the process is interrupted or a poll fails.
+#[instrument(skip(client, trace, output_format, compact), fields(
+    trace_id = %trace.map(|t| t.trace_id_hex()).unwrap_or_default(),
+    request_id = %trace.map(|t| t.request_id().to_string()).unwrap_or_default(),
+))]
+pub async fn tail_events(
+    client: &HttpClient,
+    account_id: Option<&str>,
+    user_id: Option<&str>,
+    event_type: Option<&str>,
+    trace: Option<&TraceContext>,
+    output_format: OutputFormat,
+    compact: bool,
+) -> Result<()> {
+    let mut since: Option<String> = None;
+    let mut seen: HashSet<String> = HashSet::new();
+    loop {
+        let response = client
+            .admin_list_events(account_id, user_id, event_type, since.as_deref(), None, trace)
+            .await?;
+        if let Some(events) = response.as_array() {
+            for event in events {
+                let event_id = json_str(event, "id").map(str::to_string);
+                if let Some(event_id) = &event_id {
+                    if !seen.insert(event_id.clone()) {
+                        continue;
+                    }
+                }
+                output_success(&annotate_trace(event.clone(), trace), output_format, compact);
+                if let Some(timestamp) = json_str(event, "timestamp") {
+                    since = Some(timestamp.to_string());
+                }
+            }
+        }
+        // The server's `since` filter is inclusive, so the boundary event would
+        // otherwise be re-fetched and re-printed on every tick; `seen` is what
+        // actually prevents duplicates, capped so it can't grow unbounded.
+        if seen.len() > 1024 {
+            seen.clear();
+            // Keep the ids at the `since` boundary, which the next poll returns again.
+            if let Some(events) = response.as_array() {
+                for event in events {
+                    if json_str(event, "timestamp") == since.as_deref() {
+                        if let Some(event_id) = json_str(event, "id") {
+                            seen.insert(event_id.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_user_action_creates_when_absent() {
+        assert_eq!(plan_user_action(None, "admin"), UserAction::Create);
+    }
+
+    #[test]
+    fn plan_user_action_updates_on_role_mismatch() {
+        assert_eq!(plan_user_action(Some("viewer"), "admin"), UserAction::SetRole);
+    }
+
+    #[test]
+    fn plan_user_action_unchanged_when_role_matches() {
+        assert_eq!(plan_user_action(Some("admin"), "admin"), UserAction::Unchanged);
+    }
+
+    #[test]
+    fn should_prune_user_never_prunes_account_admin() {
+        let desired: HashSet<&str> = HashSet::new();
+        assert!(!should_prune_user("root", "root", &desired));
+    }
+
+    #[test]
+    fn should_prune_user_prunes_users_not_in_manifest() {
+        let desired: HashSet<&str> = HashSet::new();
+        assert!(should_prune_user("stray-user", "root", &desired));
+    }
+
+    #[test]
+    fn should_prune_user_keeps_users_still_in_manifest() {
+        let mut desired: HashSet<&str> = HashSet::new();
+        desired.insert("alice");
+        assert!(!should_prune_user("alice", "root", &desired));
+    }
+
+    #[test]
+    fn parse_manifest_reads_yaml_by_extension() {
+        let manifest = parse_manifest(
+            Path::new("manifest.yaml"),
+            "accounts:\n  - account_id: acme\n    admin_user_id: root\n    users: []\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.accounts.len(), 1);
+        assert_eq!(manifest.accounts[0].account_id, "acme");
+    }
+
+    #[test]
+    fn parse_manifest_reads_json_by_default() {
+        let manifest = parse_manifest(
+            Path::new("manifest.json"),
+            r#"{"accounts": [{"account_id": "acme", "admin_user_id": "root"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.accounts.len(), 1);
+        assert_eq!(manifest.accounts[0].account_id, "acme");
+    }
+}
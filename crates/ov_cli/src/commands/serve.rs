@@ -0,0 +1,216 @@
+use super::admin;
+use crate::client::HttpClient;
+use crate::error::Error;
+use crate::trace::TraceContext;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Starts a local HTTP gateway that re-exposes the admin surface as REST endpoints,
+/// so internal tooling can drive provisioning over localhost without shelling out
+/// to the CLI binary itself. Every route is a thin translation to the matching
+/// `HttpClient` call (`/apply` goes through `admin::run_apply`) on the same client
+/// the CLI uses.
+pub async fn serve(client: HttpClient, address: &str) -> crate::error::Result<()> {
+    let addr: SocketAddr = address
+        .parse()
+        .map_err(|_| Error::InvalidArgument(format!("invalid listen address: {address}")))?;
+    let client = Arc::new(client);
+
+    let make_svc = make_service_fn(move |_conn| {
+        let client = client.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let client = client.clone();
+                async move { Ok::<_, Infallible>(handle(client, req).await) }
+            }))
+        }
+    });
+
+    let server = Server::bind(&addr).serve(make_svc);
+    let server = server.with_graceful_shutdown(shutdown_signal());
+
+    if let Err(err) = server.await {
+        return Err(Error::Other(format!("serve: {err}")));
+    }
+    Ok(())
+}
+
+async fn shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Builds the trace context for one inbound request from its `X-Request-Id` header
+/// (falling back to a fresh one), so the resulting `client.admin_*` call and its
+/// response can be correlated back to this request in server-side logs.
+fn trace_from_headers(req: &Request<Body>) -> TraceContext {
+    let request_id = req
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok());
+    TraceContext::from_args(None, request_id).unwrap_or_else(|_| TraceContext::generate())
+}
+
+async fn handle(client: Arc<HttpClient>, req: Request<Body>) -> Response<Body> {
+    let wants_stream = req
+        .headers()
+        .get(hyper::header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.contains("text/event-stream"))
+        .unwrap_or(false);
+
+    let trace = trace_from_headers(&req);
+    let trace = Some(&trace);
+
+    let method = req.method().clone();
+    let segments: Vec<String> = req
+        .uri()
+        .path()
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+    let segment_refs: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let result = match (&method, segment_refs.as_slice()) {
+        (&Method::POST, ["accounts"]) => {
+            let body = read_json(req).await;
+            match (body.get("account_id").and_then(Value::as_str), body.get("admin_user_id").and_then(Value::as_str)) {
+                (Some(account_id), Some(admin_user_id)) => {
+                    client.admin_create_account(account_id, admin_user_id, trace).await
+                }
+                _ => Err(Error::InvalidArgument("account_id and admin_user_id are required".into())),
+            }
+        }
+        (&Method::GET, ["accounts"]) => client.admin_list_accounts(trace).await,
+        (&Method::DELETE, ["accounts", account_id]) => client.admin_delete_account(account_id, trace).await,
+        (&Method::POST, ["accounts", account_id, "users"]) => {
+            let body = read_json(req).await;
+            match (body.get("user_id").and_then(Value::as_str), body.get("role").and_then(Value::as_str)) {
+                (Some(user_id), Some(role)) => {
+                    client.admin_register_user(account_id, user_id, role, trace).await
+                }
+                _ => Err(Error::InvalidArgument("user_id and role are required".into())),
+            }
+        }
+        (&Method::GET, ["accounts", account_id, "users"]) => client.admin_list_users(account_id, trace).await,
+        (&Method::DELETE, ["accounts", account_id, "users", user_id]) => {
+            client.admin_remove_user(account_id, user_id, trace).await
+        }
+        (&Method::PUT, ["accounts", account_id, "users", user_id, "role"]) => {
+            let body = read_json(req).await;
+            match body.get("role").and_then(Value::as_str) {
+                Some(role) => client.admin_set_role(account_id, user_id, role, trace).await,
+                None => Err(Error::InvalidArgument("role is required".into())),
+            }
+        }
+        (&Method::POST, ["accounts", account_id, "users", user_id, "key"]) => {
+            client.admin_regenerate_key(account_id, user_id, trace).await
+        }
+        (&Method::POST, ["invitation-tokens"]) => {
+            let body = read_json(req).await;
+            let max_uses = body.get("max_uses").and_then(Value::as_i64);
+            let expires_at = body.get("expires_at").and_then(Value::as_str);
+            client.admin_create_invitation_token(max_uses, expires_at, false, trace).await
+        }
+        (&Method::GET, ["invitation-tokens"]) => client.admin_list_invitation_tokens(trace).await,
+        (&Method::GET, ["events"]) => {
+            let query = parse_query(req.uri().query());
+            let account_id = query.get("account_id").map(String::as_str);
+            let user_id = query.get("user_id").map(String::as_str);
+            let event_type = query.get("event_type").map(String::as_str);
+            let since = query.get("since").map(String::as_str);
+            let limit = query.get("limit").and_then(|v| v.parse::<i64>().ok());
+            client
+                .admin_list_events(account_id, user_id, event_type, since, limit, trace)
+                .await
+        }
+        (&Method::DELETE, ["invitation-tokens", token_id]) => {
+            client.admin_revoke_invitation_token(token_id, trace).await
+        }
+        (&Method::POST, ["apply"]) => {
+            let body = read_json(req).await;
+            let prune = body.get("prune").and_then(Value::as_bool).unwrap_or(false);
+            let dry_run = body.get("dry_run").and_then(Value::as_bool).unwrap_or(false);
+            match serde_json::from_value(body) {
+                Ok(manifest) => admin::run_apply(client.as_ref(), &manifest, prune, dry_run, trace).await,
+                Err(err) => Err(Error::InvalidArgument(format!("invalid manifest: {err}"))),
+            }
+        }
+        (&Method::POST, ["register"]) => {
+            let body = read_json(req).await;
+            match (
+                body.get("invitation_token").and_then(Value::as_str),
+                body.get("account_id").and_then(Value::as_str),
+                body.get("admin_user_id").and_then(Value::as_str),
+            ) {
+                (Some(invitation_token), Some(account_id), Some(admin_user_id)) => {
+                    client.register_account(invitation_token, account_id, admin_user_id, trace).await
+                }
+                _ => Err(Error::InvalidArgument(
+                    "invitation_token, account_id and admin_user_id are required".into(),
+                )),
+            }
+        }
+        _ => return json_response(StatusCode::NOT_FOUND, json!({"error": "not found"})),
+    };
+
+    let is_list_route = method == Method::GET
+        && (segment_refs == ["accounts"]
+            || segment_refs == ["invitation-tokens"]
+            || segment_refs == ["events"]
+            || matches!(segment_refs.as_slice(), ["accounts", _, "users"]));
+
+    match result {
+        Ok(value) if wants_stream && is_list_route => event_stream_response(value),
+        Ok(value) => json_response(StatusCode::OK, json!({"success": true, "data": value})),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn read_json(req: Request<Body>) -> Value {
+    let bytes = hyper::body::to_bytes(req.into_body()).await.unwrap_or_default();
+    serde_json::from_slice(&bytes).unwrap_or(Value::Null)
+}
+
+fn parse_query(raw: Option<&str>) -> std::collections::HashMap<String, String> {
+    raw.map(|raw| url::form_urlencoded::parse(raw.as_bytes()).into_owned().collect())
+        .unwrap_or_default()
+}
+
+fn json_response(status: StatusCode, body: Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+fn event_stream_response(value: Value) -> Response<Body> {
+    let items = value.as_array().cloned().unwrap_or_else(|| vec![value]);
+    let mut body = String::new();
+    for item in items {
+        body.push_str("data: ");
+        body.push_str(&item.to_string());
+        body.push_str("\n\n");
+    }
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(hyper::header::CONTENT_TYPE, "text/event-stream")
+        .body(Body::from(body))
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+fn error_response(err: Error) -> Response<Body> {
+    let status = match &err {
+        Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+        Error::NotFound(_) => StatusCode::NOT_FOUND,
+        Error::InvalidArgument(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    json_response(status, json!({"success": false, "error": err.to_string()}))
+}
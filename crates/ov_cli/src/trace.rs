@@ -0,0 +1,98 @@
+use crate::error::{Error, Result};
+
+/// A W3C trace-context identifier pair carried on every outgoing admin request so a
+/// failed call can be correlated with server-side logs, per the `traceparent` header
+/// format defined in https://www.w3.org/TR/trace-context/.
+#[derive(Debug, Clone)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    request_id: String,
+}
+
+impl TraceContext {
+    /// Generates a fresh random trace-id, span-id, and request-id.
+    pub fn generate() -> Self {
+        Self {
+            trace_id: rand::random(),
+            span_id: rand::random(),
+            request_id: uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// Builds a context from CLI-supplied values, falling back to freshly generated
+    /// ones for anything left unspecified. `trace_id_hex` must be 32 hex chars.
+    pub fn from_args(trace_id_hex: Option<&str>, request_id: Option<&str>) -> Result<Self> {
+        let mut ctx = Self::generate();
+        if let Some(hex) = trace_id_hex {
+            ctx.trace_id = parse_hex_bytes(hex)
+                .ok_or_else(|| Error::InvalidArgument(format!("invalid trace id: {hex}")))?;
+        }
+        if let Some(request_id) = request_id {
+            ctx.request_id = request_id.to_string();
+        }
+        Ok(ctx)
+    }
+
+    /// Formats the `traceparent` header value: `00-{trace-id}-{span-id}-01`.
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-01",
+            hex::encode(self.trace_id),
+            hex::encode(self.span_id)
+        )
+    }
+
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// Hex-encoded trace-id, suitable for recording on a tracing span so nested
+    /// HTTP calls made within it inherit the same id.
+    pub fn trace_id_hex(&self) -> String {
+        hex::encode(self.trace_id)
+    }
+}
+
+fn parse_hex_bytes<const N: usize>(hex_str: &str) -> Option<[u8; N]> {
+    if hex_str.len() != N * 2 {
+        return None;
+    }
+    let bytes = hex::decode(hex_str).ok()?;
+    bytes.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traceparent_has_w3c_shape() {
+        let ctx = TraceContext::from_args(Some("0af7651916cd43dd8448eb211c80319c"), None).unwrap();
+        let header = ctx.traceparent();
+        assert!(header.starts_with("00-0af7651916cd43dd8448eb211c80319c-"));
+        assert!(header.ends_with("-01"));
+    }
+
+    #[test]
+    fn trace_id_hex_round_trips_supplied_id() {
+        let ctx = TraceContext::from_args(Some("0af7651916cd43dd8448eb211c80319c"), None).unwrap();
+        assert_eq!(ctx.trace_id_hex(), "0af7651916cd43dd8448eb211c80319c");
+    }
+
+    #[test]
+    fn from_args_rejects_wrong_length_trace_id() {
+        assert!(TraceContext::from_args(Some("deadbeef"), None).is_err());
+    }
+
+    #[test]
+    fn from_args_rejects_non_hex_trace_id() {
+        assert!(TraceContext::from_args(Some("zz".repeat(16).as_str()), None).is_err());
+    }
+
+    #[test]
+    fn from_args_uses_supplied_request_id() {
+        let ctx = TraceContext::from_args(None, Some("req-123")).unwrap();
+        assert_eq!(ctx.request_id(), "req-123");
+    }
+}